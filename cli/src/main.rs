@@ -9,7 +9,7 @@ extern crate rand;
 use std::io::{self, Write};
 use std::process::exit;
 
-use ksuid::Ksuid;
+use ksuid::{Error, Ksuid};
 use rand::Rng;
 
 const USAGE: &str = "
@@ -62,14 +62,14 @@ fn inspect(args: Args) {
         } else if uid.len() == 27 {
             Ksuid::from_base62(uid.as_ref())
         } else {
-            Err(io::Error::new(io::ErrorKind::InvalidData, "KSUID must be either 27 characters (Base62) or 40 characters (Hex) in length"))
+            Err(Error::InvalidLength)
         };
 
         let ksuid = match res {
             Ok(id) => id,
             Err(e) => {
                 let _ = writeln!(io::stderr(), "Invalid KSUID: {}", e);
-                exit(e.raw_os_error().unwrap_or(2));
+                exit(2);
             }
         };
 
@@ -12,10 +12,10 @@
 //! purpose use, callers should use `conversion_len_bound()` to calculate the required output
 //! buffer length.
 
-use std::io;
-
 use resize_slice::ResizeSlice;
 
+use super::error::{Error, Result};
+
 const CHAR_MAP: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 
 const BYTE_MAP: &[i8] = &[
@@ -41,6 +41,10 @@ fn b62_to_bin(c: u8) -> i8 {
 }
 
 /// An upper-bound on the length of the result of a generic base conversion.
+///
+/// This relies on floating point logarithms and so needs `std`; the fixed-length buffers used
+/// to encode/decode a whole KSUID don't need it.
+#[cfg(feature = "std")]
 pub fn conversion_len_bound(len: usize, in_base: usize, out_base: usize) -> usize {
     let out = len as f64 * ((in_base as f64).ln() / (out_base as f64).ln());
     out as usize + 1
@@ -84,7 +88,11 @@ fn change_base(mut num: &mut [u8], out: &mut [u8], in_base: usize, out_base: usi
 }
 
 /// Base62-encode `input`, placing the result into `output`.
-pub fn encode_raw(input: &mut [u8], output: &mut [u8]) {
+///
+/// `input` is clobbered and `output` must be preallocated; see the module documentation for
+/// why. Use [`conversion_len_bound`](fn.conversion_len_bound.html) to size `output` for inputs
+/// other than a KSUID's fixed-size representation.
+pub fn encode_into(input: &mut [u8], output: &mut [u8]) {
     change_base(input, output, 256, 62);
     for b in output.iter_mut() {
         *b = CHAR_MAP[usize::from(*b)];
@@ -93,16 +101,18 @@ pub fn encode_raw(input: &mut [u8], output: &mut [u8]) {
 
 /// Decode the Base62-encoded data in `input`, placing the result into `output`. If `input`
 /// contains any characters which do not match `/[0-9A-Za-z]/`, an error will be returned.
-pub fn decode_raw(input: &mut [u8], output: &mut [u8]) -> io::Result<()> {
+///
+/// `input` is clobbered and `output` must be preallocated; see the module documentation for why.
+pub fn decode_into(input: &mut [u8], output: &mut [u8]) -> Result<()> {
     // Map each ASCII-encoded Base62 character to its binary value.
     for c in input.iter_mut() {
         if *c & 0x80 != 0 {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Non-ASCII character in input"));
+            return Err(Error::InvalidBase62Char);
         }
 
         let b = b62_to_bin(*c);
         if b < 0 {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid base62 character in input"));
+            return Err(Error::InvalidBase62Char);
         }
 
         *c = b as u8;
@@ -0,0 +1,37 @@
+//! The error type returned by this crate's parsing routines.
+//!
+//! This is a crate-local type rather than `std::io::Error` so that the core, allocation-free
+//! parts of the crate (the `Ksuid` type and the fixed-size `base62`/`hex` encoders and decoders)
+//! compile under `#![no_std]`.
+
+use core::fmt;
+
+/// The result type returned by this crate's fallible operations.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// An error produced while parsing or decoding a [`Ksuid`](../struct.Ksuid.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// The input did not have the length required for the representation being parsed, or (for
+    /// Base62) it encodes a value larger than the largest KSUID.
+    InvalidLength,
+    /// The input contained a byte outside the Base62 alphabet (`[0-9A-Za-z]`).
+    InvalidBase62Char,
+    /// The input contained a byte outside the hex alphabet (`[0-9A-Fa-f]`).
+    InvalidHexChar,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match *self {
+            Error::InvalidLength => "input had an unexpected length",
+            Error::InvalidBase62Char => "invalid Base62 character in input",
+            Error::InvalidHexChar => "invalid hex character in input",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
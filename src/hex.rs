@@ -0,0 +1,90 @@
+//! Utilities for hex encoding of data.
+//!
+//! Like [`base62`](../base62/index.html), this module operates on caller-provided buffers
+//! rather than allocating, so it is available under `#![no_std]`.
+
+use super::error::{Error, Result};
+
+const CHAR_MAP: &[u8] = b"0123456789ABCDEF";
+
+/// Get the numeric value corresponding to the given ASCII hex digit (`/[0-9A-Fa-f]/`).
+fn hex_digit(c: u8) -> Result<u8> {
+    let upper = if c >= b'a' && c <= b'z' { c - 32 } else { c };
+    CHAR_MAP.iter()
+        .position(|&d| d == upper)
+        .map(|idx| idx as u8)
+        .ok_or(Error::InvalidHexChar)
+}
+
+/// Hex-encode `input`, placing the result into `output`.
+///
+/// `output` must be exactly twice as long as `input`.
+pub fn encode_into(input: &[u8], output: &mut [u8]) {
+    for (b, pair) in input.iter().zip(output.chunks_mut(2)) {
+        pair[0] = CHAR_MAP[(b / 16) as usize];
+        pair[1] = CHAR_MAP[(b % 16) as usize];
+    }
+}
+
+/// Decode the hex-encoded data in `input`, placing the result into `output`.
+///
+/// `input` must be exactly twice as long as `output`, or `Error::InvalidLength` is returned. If
+/// `input` contains any characters which do not match `/[0-9A-Fa-f]/`, `Error::InvalidHexChar` is
+/// returned.
+pub fn decode_into(input: &[u8], output: &mut [u8]) -> Result<()> {
+    if input.len() != 2 * output.len() {
+        return Err(Error::InvalidLength);
+    }
+
+    for (pair, place) in input.chunks(2).zip(output.iter_mut()) {
+        let upper = hex_digit(pair[0])?;
+        let lower = hex_digit(pair[1])?;
+        *place = (upper * 16 + lower) as u8;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let input = [0x00, 0x7f, 0xff, 0x42];
+        let mut encoded = [0; 8];
+        encode_into(&input, &mut encoded);
+        assert_eq!(&encoded, b"007FFF42");
+
+        let mut decoded = [0; 4];
+        decode_into(&encoded, &mut decoded).unwrap();
+        assert_eq!(decoded, input);
+    }
+
+    #[test]
+    fn decode_is_case_insensitive() {
+        let mut lower = [0; 2];
+        decode_into(b"ffff", &mut lower).unwrap();
+
+        let mut upper = [0; 2];
+        decode_into(b"FFFF", &mut upper).unwrap();
+
+        assert_eq!(lower, upper);
+        assert_eq!(lower, [0xff, 0xff]);
+    }
+
+    #[test]
+    fn decode_rejects_invalid_chars() {
+        let mut output = [0; 1];
+        assert_eq!(decode_into(b"zz", &mut output), Err(Error::InvalidHexChar));
+    }
+
+    #[test]
+    fn decode_rejects_mismatched_length() {
+        // `input` is one char shorter than `2 * output.len()`, which would misalign
+        // `input.chunks(2)` against `output.iter_mut()` and index out of bounds on the final,
+        // short chunk if not caught up front.
+        let mut output = [0; 2];
+        assert_eq!(decode_into(b"000", &mut output), Err(Error::InvalidLength));
+    }
+}
@@ -13,21 +13,46 @@
 //! The author of this package is not affiliated with Segment.
 
 #![feature(test)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate byteorder;
-extern crate rand;
 extern crate resize_slice;
+
+// The legacy `Rand`/`Rng` traits in this pre-1.0 `rand` always reach for an OS RNG (`thread_rng`,
+// `rand::random()`), so the dependency itself — not just its call sites — has no `no_std` story.
+// Keep it out of the dependency graph entirely unless `std` is enabled; `no_std` callers mint ids
+// via `Ksuid::new_with`, supplying their own randomness however they see fit.
+#[cfg(feature = "std")]
+extern crate rand;
+
+#[cfg(feature = "std")]
 extern crate time;
 
-mod base62;
+#[cfg(feature = "serde")]
+extern crate serde;
 
-use std::io;
-use std::ascii::AsciiExt;
+#[cfg(feature = "name")]
+extern crate sha1;
+
+pub mod base62;
+pub mod hex;
+mod error;
+
+#[cfg(feature = "serde")]
+mod serde_support;
+
+use core::{fmt, str};
 
 use byteorder::{ByteOrder, BigEndian};
-use time::{Timespec, Duration};
+
+#[cfg(feature = "std")]
 use rand::{Rng, Rand};
 
+#[cfg(feature = "std")]
+use time::{Timespec, Duration};
+
+pub use error::{Error, Result};
+
 /// The KSUID epoch, 1.4 billion seconds after the UNIX epoch.
 ///
 /// ```
@@ -35,23 +60,15 @@ use rand::{Rng, Rand};
 /// # extern crate time;
 /// assert_eq!(ksuid::EPOCH, time::strptime("2014-5-13 16:53:20", "%Y-%m-%d %T").unwrap().to_timespec());
 /// ```
+#[cfg(feature = "std")]
 pub const EPOCH: Timespec = Timespec {sec: 1_400_000_000, nsec: 0};
 
 const LEN: usize = 20;
 const EMPTY: [u8; LEN] = [0; LEN];
 const BASE62_LEN: usize = 27;
 const HEX_LEN: usize = 40;
-const HEX_DIGITS: &[u8] = b"0123456789ABCDEF";
 const MAX_BASE62_KSUID: &[u8] = b"aWgEPTl1tmebfsQzFP4bxwgy80V";
 
-/// Get the numeric value corresponding to the given ASCII hex digit.
-fn hex_digit(c: u8) -> io::Result<u8> {
-    HEX_DIGITS.iter()
-        .position(|d| c.eq_ignore_ascii_case(d))
-        .map(|idx| idx as u8)
-        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Invalid hex character in input"))
-}
-
 /// A K-Sortable Unique IDentifier.
 ///
 /// The first 4 bytes are a big-endian encoded, unsigned timestamp indicating when the UUID was
@@ -79,7 +96,18 @@ impl Ksuid {
         ret
     }
 
+    /// Create a new identifier with the given timestamp and payload.
+    ///
+    /// This is identical to [`Ksuid::new`](#method.new); it exists as the explicit-timestamp
+    /// counterpart to [`Ksuid::with_payload`](#method.with_payload) for callers (e.g. on
+    /// embedded or WASM targets built without the `std` feature) that have no system clock and
+    /// must supply a timestamp from elsewhere.
+    pub fn new_with(timestamp: u32, payload: [u8; 16]) -> Self {
+        Self::new(timestamp, payload)
+    }
+
     /// Create a new identifier with a current timestamp and the given payload.
+    #[cfg(feature = "std")]
     pub fn with_payload(payload: [u8; 16]) -> Self {
         // TODO: check for overflow in timestamp
         let elapsed = time::get_time() - EPOCH;
@@ -93,6 +121,7 @@ impl Ksuid {
     /// calling `generate()` in a loop, caching the generator can increase performance. See the
     /// documentation of [`rand::random()`](https://doc.rust-lang.org/rand/rand/fn.random.html) for
     /// an example.
+    #[cfg(feature = "std")]
     pub fn generate() -> Self {
         rand::random()
     }
@@ -107,16 +136,16 @@ impl Ksuid {
     /// let id = ksuid::Ksuid::from_base62("0o5Fs0EELR0fUjHjbCnEtdUwQe3").unwrap();
     /// assert_eq!(id.timestamp(), 94985761);
     /// ```
-    pub fn from_base62(s: &str) -> io::Result<Self> {
+    pub fn from_base62(s: &str) -> Result<Self> {
         let bytes = s.as_bytes();
         if bytes.len() != BASE62_LEN || bytes > MAX_BASE62_KSUID {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid id"));
+            return Err(Error::InvalidLength);
         }
 
         let mut ret = Ksuid(EMPTY);
         let mut scratch = [0; BASE62_LEN];
         scratch.clone_from_slice(bytes);
-        base62::decode_raw(scratch.as_mut(), ret.0.as_mut())?;
+        base62::decode_into(scratch.as_mut(), ret.0.as_mut())?;
         Ok(ret)
     }
 
@@ -130,27 +159,22 @@ impl Ksuid {
     /// let id = ksuid::Ksuid::from_hex("05a95e21D7B6Fe8CD7Cff211704d8E7B9421210B").unwrap();
     /// assert_eq!(id.timestamp(), 94985761);
     /// ```
-    pub fn from_hex(hex: &str) -> io::Result<Self> {
-        if hex.len() != HEX_LEN {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Hex string must be 40 bytes long"));
+    pub fn from_hex(s: &str) -> Result<Self> {
+        if s.len() != HEX_LEN {
+            return Err(Error::InvalidLength);
         }
 
         let mut ret = Ksuid(EMPTY);
-        for (pair, place) in hex.as_bytes().chunks(2).zip(ret.0.iter_mut()) {
-            let upper = hex_digit(pair[0])?;
-            let lower = hex_digit(pair[1])?;
-            *place = (upper * 16 + lower) as u8;
-        }
-
+        hex::decode_into(s.as_bytes(), ret.0.as_mut())?;
         Ok(ret)
     }
 
     /// Parse an identifier from its binary representation.
     ///
     /// `raw` must be exactly 20 bytes long.
-    pub fn from_bytes(raw: &[u8]) -> io::Result<Self> {
+    pub fn from_bytes(raw: &[u8]) -> Result<Self> {
         if raw.len() != LEN {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Ksuids are 20 bytes long"));
+            return Err(Error::InvalidLength);
         }
 
         let mut ret = Ksuid(EMPTY);
@@ -166,25 +190,46 @@ impl Ksuid {
     /// let id = ksuid::Ksuid::new(::std::u32::MAX, [255; 16]);
     /// assert_eq!(id.to_base62(), "aWgEPTl1tmebfsQzFP4bxwgy80V");
     /// ```
+    #[cfg(feature = "std")]
     pub fn to_base62(&self) -> String {
-        let mut scratch = self.0;
-        let mut out = vec![0; BASE62_LEN];
-        base62::encode_raw(scratch.as_mut(), out.as_mut());
+        self.display_base62().to_string()
+    }
 
-        // This is valid because base 62 encoded data contains only ASCII alphanumeric characters.
-        unsafe { String::from_utf8_unchecked(out) }
+    /// A zero-allocation `Display` adapter for the Base62 encoding of this identifier.
+    ///
+    /// Unlike [`Ksuid::to_base62`](struct.Ksuid.html#method.to_base62), this encodes into a
+    /// stack buffer and writes straight through the formatter, so logging or formatting a KSUID
+    /// doesn't touch the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let id = ksuid::Ksuid::new(::std::u32::MAX, [255; 16]);
+    /// assert_eq!(id.display_base62().to_string(), "aWgEPTl1tmebfsQzFP4bxwgy80V");
+    /// ```
+    pub fn display_base62(&self) -> DisplayBase62 {
+        DisplayBase62(*self)
+    }
+
+    /// Base62-encode this identifier into a stack buffer, without allocating.
+    ///
+    /// Shared by [`DisplayBase62`](struct.DisplayBase62.html) and the `serde` support so both
+    /// stay `no_std`-friendly instead of routing through [`Ksuid::to_base62`](#method.to_base62).
+    fn base62_bytes(&self) -> [u8; BASE62_LEN] {
+        let mut scratch = self.0;
+        let mut out = [0; BASE62_LEN];
+        base62::encode_into(scratch.as_mut(), out.as_mut());
+        out
     }
 
     /// The hex-encoded version of this identifier.
+    #[cfg(feature = "std")]
     pub fn to_hex(&self) -> String {
-        let mut ret = Vec::with_capacity(HEX_LEN);
-        for b in self.as_bytes() {
-            ret.push(HEX_DIGITS[(b / 16) as usize]);
-            ret.push(HEX_DIGITS[(b % 16) as usize]);
-        }
+        let mut out = vec![0; HEX_LEN];
+        hex::encode_into(self.as_bytes(), out.as_mut());
 
-        // This is valid because we push only ASCII characters from `HEX_DIGITS` into `ret`.
-        unsafe { String::from_utf8_unchecked(ret) }
+        // This is valid because hex encoded data contains only ASCII characters.
+        unsafe { String::from_utf8_unchecked(out) }
     }
 
     /// The 20-byte binary representation of this identifier.
@@ -206,11 +251,13 @@ impl Ksuid {
     }
 
     /// The number of seconds after the UNIX epoch when this identifier was created.
+    #[cfg(feature = "std")]
     pub fn time(&self) -> Timespec {
         EPOCH + Duration::seconds(self.timestamp().into())
     }
 
     /// Set the timestamp of the identifier to the given time.
+    #[cfg(feature = "std")]
     pub fn set_time(&mut self, time: Timespec) {
         let dur = time - EPOCH;
         self.set_timestamp(dur.num_seconds() as u32);
@@ -225,19 +272,288 @@ impl Ksuid {
     pub fn set_payload(&mut self, payload: [u8; 16]) {
         (&mut self.0[4..]).copy_from_slice(payload.as_ref());
     }
+
+    /// The identifier immediately following this one, treating the 20-byte representation as a
+    /// big-endian 160-bit integer.
+    ///
+    /// Saturates at the all-ones id rather than wrapping around to [`Ksuid::min_for_timestamp`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let id = ksuid::Ksuid::new(1000, [0; 16]);
+    /// assert_eq!(id.next().payload(), [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    /// ```
+    pub fn next(&self) -> Self {
+        let mut ret = *self;
+        for b in ret.0.iter_mut().rev() {
+            if *b == 0xff {
+                *b = 0;
+            } else {
+                *b += 1;
+                return ret;
+            }
+        }
+
+        // Every byte was `0xff`; saturate instead of wrapping to all-zeros.
+        Ksuid([0xff; LEN])
+    }
+
+    /// The identifier immediately preceding this one, treating the 20-byte representation as a
+    /// big-endian 160-bit integer.
+    ///
+    /// Saturates at the all-zeros id rather than wrapping around to [`Ksuid::max_for_timestamp`].
+    pub fn prev(&self) -> Self {
+        let mut ret = *self;
+        for b in ret.0.iter_mut().rev() {
+            if *b == 0 {
+                *b = 0xff;
+            } else {
+                *b -= 1;
+                return ret;
+            }
+        }
+
+        // Every byte was `0`; saturate instead of wrapping to all-ones.
+        Ksuid(EMPTY)
+    }
+
+    /// The smallest identifier with the given timestamp, i.e. one with an all-zeros payload.
+    ///
+    /// Paired with [`Ksuid::max_for_timestamp`], this lets callers express "every id created
+    /// during second `ts`" as the inclusive range `[min_for_timestamp(ts), max_for_timestamp(ts)]`
+    /// when querying a store keyed on [`Ksuid::as_bytes`].
+    pub fn min_for_timestamp(ts: u32) -> Self {
+        Self::new(ts, [0; 16])
+    }
+
+    /// The largest identifier with the given timestamp, i.e. one with an all-ones payload.
+    pub fn max_for_timestamp(ts: u32) -> Self {
+        Self::new(ts, [0xff; 16])
+    }
 }
 
+#[cfg(feature = "std")]
 impl Rand for Ksuid {
     fn rand<R: Rng>(rng: &mut R) -> Self {
         Self::with_payload(rng.gen())
     }
 }
 
+/// A zero-allocation `Display` adapter for the Base62 encoding of a [`Ksuid`].
+///
+/// Returned by [`Ksuid::display_base62`](struct.Ksuid.html#method.display_base62).
+pub struct DisplayBase62(Ksuid);
+
+impl fmt::Display for DisplayBase62 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let out = self.0.base62_bytes();
+
+        // This is valid because base 62 encoded data contains only ASCII alphanumeric characters.
+        f.write_str(unsafe { str::from_utf8_unchecked(out.as_ref()) })
+    }
+}
+
+#[cfg(feature = "name")]
+impl Ksuid {
+    /// Derive a deterministic, namespaced identifier from a name, in the spirit of UUID's v3/v5.
+    ///
+    /// The payload is the first 16 bytes of the SHA-1 digest of `namespace.as_bytes()`
+    /// concatenated with `name`, so the same `(namespace, name)` pair always produces the same
+    /// payload; this is useful for content-addressed or idempotent workflows such as dedup keys.
+    /// The caller-supplied `timestamp` is used as-is, since a hash carries no notion of time.
+    pub fn from_name(namespace: &Ksuid, name: &[u8], timestamp: u32) -> Self {
+        let mut hasher = sha1::Sha1::new();
+        hasher.update(namespace.as_bytes());
+        hasher.update(name);
+
+        let digest = hasher.digest().bytes();
+        let mut payload = [0; 16];
+        payload.copy_from_slice(&digest[..16]);
+
+        Self::new(timestamp, payload)
+    }
+}
+
+/// Interpret a 16-byte payload as a big-endian, unsigned 128-bit integer.
+#[cfg(feature = "std")]
+fn payload_to_u128(payload: &[u8]) -> u128 {
+    payload.iter().fold(0u128, |acc, &b| (acc << 8) | u128::from(b))
+}
+
+/// The inverse of [`payload_to_u128`](fn.payload_to_u128.html).
+#[cfg(feature = "std")]
+fn u128_to_payload(mut val: u128) -> [u8; 16] {
+    let mut out = [0; 16];
+    for b in out.iter_mut().rev() {
+        *b = (val & 0xff) as u8;
+        val >>= 8;
+    }
+    out
+}
+
+/// A generator that produces strictly increasing [`Ksuid`](struct.Ksuid.html)s, even when
+/// several are minted within the same second.
+///
+/// `Ksuid::generate()` picks a fresh random payload on every call, so two ids created in the
+/// same second have no defined relative order. `MonotonicGenerator` instead remembers the last
+/// id it produced: if the clock has ticked forward since then, it behaves just like
+/// `Ksuid::generate()`; otherwise it reuses the timestamp and increments the previous payload
+/// (treated as a big-endian 128-bit integer) by one, so the full 160-bit value of each emitted
+/// id is strictly greater than its predecessor. Should the payload overflow, the timestamp is
+/// bumped by a second and a fresh random payload is drawn to preserve the invariant.
+///
+/// # Examples
+///
+/// ```
+/// let mut gen = ksuid::MonotonicGenerator::new();
+/// let a = gen.next();
+/// let b = gen.next();
+/// assert!(b > a);
+/// ```
+#[cfg(feature = "std")]
+pub struct MonotonicGenerator {
+    last: Option<Ksuid>,
+}
+
+#[cfg(feature = "std")]
+impl MonotonicGenerator {
+    /// Create a new generator with no previously emitted id.
+    pub fn new() -> Self {
+        MonotonicGenerator { last: None }
+    }
+
+    /// Produce the next id, guaranteed to be strictly greater than every id this generator has
+    /// previously produced.
+    pub fn next(&mut self) -> Ksuid {
+        let elapsed = time::get_time() - EPOCH;
+        let ts = elapsed.num_seconds() as u32;
+
+        let next = Self::advance(self.last, ts);
+        self.last = Some(next);
+        next
+    }
+
+    /// The logic behind `next()`, parameterized on the current timestamp instead of reading the
+    /// wall clock directly, so it can be exercised deterministically in tests.
+    ///
+    /// Uses `>=`, not `==`, against `prev`'s timestamp: the wall clock can step backwards (NTP
+    /// correction, VM pause/resume, leap second), and treating that as "a new second" would mint
+    /// an id whose timestamp is less than `prev`'s, sorting it *before* `prev` and breaking
+    /// monotonicity.
+    fn advance(last: Option<Ksuid>, ts: u32) -> Ksuid {
+        match last {
+            Some(prev) if prev.timestamp() >= ts => {
+                match payload_to_u128(prev.payload()).checked_add(1) {
+                    Some(incremented) => {
+                        let mut next = prev;
+                        next.set_payload(u128_to_payload(incremented));
+                        next
+                    }
+                    None => Ksuid::new(prev.timestamp() + 1, rand::random()),
+                }
+            }
+            _ => Ksuid::new(ts, rand::random()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for MonotonicGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     extern crate test;
     use super::*;
 
+    #[test]
+    fn next_saturates_at_all_ones() {
+        let max = Ksuid::from_bytes(&[0xff; LEN]).unwrap();
+        assert_eq!(max.next(), max);
+    }
+
+    #[test]
+    fn prev_saturates_at_all_zeros() {
+        let min = Ksuid::from_bytes(&[0; LEN]).unwrap();
+        assert_eq!(min.prev(), min);
+    }
+
+    #[test]
+    fn next_and_prev_are_inverses_away_from_the_boundary() {
+        let id = Ksuid::new(1000, [0; 16]);
+        assert_eq!(id.next().prev(), id);
+        assert_eq!(id.prev().next(), id);
+    }
+
+    #[test]
+    fn min_and_max_for_timestamp_bound_the_second() {
+        let min = Ksuid::min_for_timestamp(1000);
+        let max = Ksuid::max_for_timestamp(1000);
+
+        assert_eq!(min.timestamp(), 1000);
+        assert_eq!(max.timestamp(), 1000);
+        assert!(min < max);
+        assert_eq!(min.payload(), [0; 16].as_ref());
+        assert_eq!(max.payload(), [0xff; 16].as_ref());
+    }
+
+    #[test]
+    fn monotonic_increments_within_same_second() {
+        let prev = Ksuid::new(1000, [0; 16]);
+        let next = MonotonicGenerator::advance(Some(prev), 1000);
+
+        assert_eq!(next.timestamp(), 1000);
+        assert!(next > prev);
+    }
+
+    #[test]
+    fn monotonic_rolls_timestamp_over_on_payload_overflow() {
+        let prev = Ksuid::new(1000, [0xff; 16]);
+        let next = MonotonicGenerator::advance(Some(prev), 1000);
+
+        assert_eq!(next.timestamp(), 1001);
+        assert!(next > prev);
+    }
+
+    #[test]
+    fn monotonic_clamps_clock_regressions() {
+        let prev = Ksuid::new(1000, [0; 16]);
+        let next = MonotonicGenerator::advance(Some(prev), 999);
+
+        assert_eq!(next.timestamp(), 1000);
+        assert!(next > prev);
+    }
+
+    #[test]
+    #[cfg(feature = "name")]
+    fn from_name_is_deterministic() {
+        let namespace = Ksuid::new(1000, [1; 16]);
+
+        let a = Ksuid::from_name(&namespace, b"alice", 2000);
+        let b = Ksuid::from_name(&namespace, b"alice", 2000);
+
+        assert_eq!(a, b);
+        assert_eq!(a.timestamp(), 2000);
+    }
+
+    #[test]
+    #[cfg(feature = "name")]
+    fn from_name_differs_by_name_and_namespace() {
+        let namespace = Ksuid::new(1000, [1; 16]);
+        let other_namespace = Ksuid::new(1000, [2; 16]);
+
+        let alice = Ksuid::from_name(&namespace, b"alice", 2000);
+        let bob = Ksuid::from_name(&namespace, b"bob", 2000);
+        let alice_elsewhere = Ksuid::from_name(&other_namespace, b"alice", 2000);
+
+        assert_ne!(alice.payload(), bob.payload());
+        assert_ne!(alice.payload(), alice_elsewhere.payload());
+    }
+
     #[bench]
     fn bench_from_base62(b: &mut test::Bencher) {
         let encoded = ::std::str::from_utf8(MAX_BASE62_KSUID).unwrap();
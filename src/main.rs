@@ -3,9 +3,7 @@ extern crate serde_derive;
 extern crate docopt;
 extern crate ksuid;
 
-use std::io;
-
-use ksuid::Ksuid;
+use ksuid::{Error, Ksuid};
 
 const USAGE: &str = "
 ksuid
@@ -50,7 +48,7 @@ fn inspect(args: Args) {
         } else if uid.len() == 27 {
             Ksuid::from_base62(uid.as_ref())
         } else {
-            Err(io::Error::new(io::ErrorKind::InvalidData, ""))
+            Err(Error::InvalidLength)
         };
 
         let ksuid = res.expect("Invalid KSUID");
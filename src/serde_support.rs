@@ -0,0 +1,85 @@
+//! `Serialize` and `Deserialize` implementations for [`Ksuid`](../struct.Ksuid.html).
+//!
+//! Human-readable formats (JSON, YAML, ...) encode the id as its Base62 string
+//! representation, matching what `from_base62()` parses. Compact, binary formats (bincode,
+//! CBOR, ...) encode the raw 20-byte representation instead, avoiding the cost of a Base62
+//! round-trip. Both directions avoid allocation, so this module doesn't require `std`.
+
+use core::{fmt, str};
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Ksuid, LEN};
+
+impl Serialize for Ksuid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let out = self.base62_bytes();
+
+            // This is valid because base 62 encoded data contains only ASCII alphanumeric
+            // characters.
+            serializer.serialize_str(unsafe { str::from_utf8_unchecked(&out) })
+        } else {
+            serializer.serialize_bytes(self.as_bytes())
+        }
+    }
+}
+
+struct KsuidVisitor;
+
+impl<'de> Visitor<'de> for KsuidVisitor {
+    type Value = Ksuid;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a 27-character Base62 string, a 40-character hex string, or {} bytes", LEN)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Ksuid, E> {
+        let res = match v.len() {
+            27 => Ksuid::from_base62(v),
+            40 => Ksuid::from_hex(v),
+            n => return Err(E::invalid_length(n, &self)),
+        };
+
+        res.map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Ksuid, E> {
+        Ksuid::from_bytes(v).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ksuid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(KsuidVisitor)
+        } else {
+            deserializer.deserialize_bytes(KsuidVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate bincode;
+    extern crate serde_json;
+    use super::*;
+
+    #[test]
+    fn human_readable_round_trip() {
+        let id = Ksuid::new(1000, [7; 16]);
+
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, format!("\"{}\"", id.display_base62()));
+        assert_eq!(serde_json::from_str::<Ksuid>(&json).unwrap(), id);
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let id = Ksuid::new(1000, [7; 16]);
+
+        let bytes = bincode::serialize(&id).unwrap();
+        assert_eq!(bincode::deserialize::<Ksuid>(&bytes).unwrap(), id);
+    }
+}